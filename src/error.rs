@@ -0,0 +1,39 @@
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ContrustError {
+    /// Two grids (or a grid and a row) that must have the same `(rows, cols)` size do not.
+    DimensionMismatch {
+        expected: (usize, usize),
+        actual: (usize, usize),
+    },
+    /// The entry at `(row, col)` is NaN or infinite.
+    NonFinite { row: usize, col: usize },
+    /// The input has no entries at all.
+    EmptyInput,
+    /// The number of entries is not a multiple of the number of columns.
+    InvalidGridLength { len: usize, cols: usize },
+}
+
+impl Display for ContrustError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ContrustError::DimensionMismatch { expected, actual } => write!(
+                f,
+                "expected a {}x{} grid, but got a {}x{} grid",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+            ContrustError::NonFinite { row, col } => {
+                write!(f, "the entry at ({}, {}) is not finite", row, col)
+            }
+            ContrustError::EmptyInput => write!(f, "the input is empty"),
+            ContrustError::InvalidGridLength { len, cols } => write!(
+                f,
+                "cannot split {} entries into rows of {} columns",
+                len, cols
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ContrustError {}
@@ -1,4 +1,10 @@
-use std::ops::{Index, IndexMut};
+use crate::error::ContrustError;
+use crate::op_alias::{AddSelf, MulScalar, SubSelf};
+use crate::utils;
+use dimensioned::typenum::{Prod, Quot};
+use dimensioned::Dimensioned;
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Div, Index, IndexMut, Mul};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Grid<T> {
@@ -8,9 +14,53 @@ pub struct Grid<T> {
 
 impl<T> Grid<T> {
     pub fn from_vec(v: Vec<T>, cols: usize) -> Grid<T> {
-        assert!(v.len() % cols == 0);
+        Self::try_from_vec(v, cols).unwrap()
+    }
 
-        Self { v, cols }
+    /// Same as `from_vec`, but returns an error instead of panicking when `v` cannot be split into rows of `cols` entries.
+    pub fn try_from_vec(v: Vec<T>, cols: usize) -> Result<Grid<T>, ContrustError> {
+        if cols == 0 || v.len() % cols != 0 {
+            return Err(ContrustError::InvalidGridLength { len: v.len(), cols });
+        }
+
+        Ok(Self { v, cols })
+    }
+
+    /// Builds a grid from row-major nested vectors. Every row must have the same length.
+    pub fn from_nested_vec(rows: Vec<Vec<T>>) -> Grid<T> {
+        Self::try_from_nested_vec(rows).unwrap()
+    }
+
+    /// Same as `from_nested_vec`, but returns an error for empty or ragged input.
+    pub fn try_from_nested_vec(rows: Vec<Vec<T>>) -> Result<Grid<T>, ContrustError> {
+        let cols = match rows.first() {
+            Some(row) if !row.is_empty() => row.len(),
+            _ => return Err(ContrustError::EmptyInput),
+        };
+        if let Some(row) = rows.iter().find(|row| row.len() != cols) {
+            return Err(ContrustError::DimensionMismatch {
+                expected: (1, cols),
+                actual: (1, row.len()),
+            });
+        }
+
+        let v = rows.into_iter().flatten().collect();
+        Ok(Grid::from_vec(v, cols))
+    }
+
+    /// Returns the entries in row-major order.
+    pub fn as_slice(&self) -> &[T] {
+        &self.v
+    }
+
+    /// Returns the entries in row-major order.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.v
+    }
+
+    /// Reinterprets the row-major entries as rows of `new_cols` entries.
+    pub fn reshape(self, new_cols: usize) -> Grid<T> {
+        Grid::from_vec(self.v, new_cols)
     }
 
     pub fn rows(&self) -> usize {
@@ -34,6 +84,19 @@ impl<T> Grid<T> {
         (0..self.cols()).map(move |col| Col { grid: self, col })
     }
 
+    /// Iterates `(row, col, &value)` in row-major order.
+    pub fn iter_indexed(&self) -> impl Iterator<Item = (usize, usize, &T)> {
+        let cols = self.cols;
+        self.v
+            .iter()
+            .enumerate()
+            .map(move |(i, item)| (i / cols, i % cols, item))
+    }
+
+    pub fn iter_rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        self.v.chunks_mut(self.cols)
+    }
+
     pub fn map_rowwise<'g, U, I, F>(&'g self, f: F) -> Grid<U>
     where
         F: FnMut(Row<'g, T>) -> I,
@@ -70,11 +133,36 @@ impl<T> Grid<T> {
         g
     }
 
-    pub fn merge_entrywise<U, V, F>(&self, right: &Grid<U>, mut f: F) -> Grid<V>
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        let v = self.v.iter().map(f).collect();
+        Grid { v, cols: self.cols }
+    }
+
+    pub fn merge_entrywise<U, V, F>(&self, right: &Grid<U>, f: F) -> Grid<V>
+    where
+        F: FnMut(&T, &U) -> V,
+    {
+        self.try_merge_entrywise(right, f).unwrap()
+    }
+
+    /// Same as `merge_entrywise`, but returns an error instead of panicking when the sizes differ.
+    pub fn try_merge_entrywise<U, V, F>(
+        &self,
+        right: &Grid<U>,
+        mut f: F,
+    ) -> Result<Grid<V>, ContrustError>
     where
         F: FnMut(&T, &U) -> V,
     {
-        assert_eq!(self.size(), right.size());
+        if self.size() != right.size() {
+            return Err(ContrustError::DimensionMismatch {
+                expected: self.size(),
+                actual: right.size(),
+            });
+        }
 
         let v = self
             .v
@@ -82,7 +170,147 @@ impl<T> Grid<T> {
             .zip(right.v.iter())
             .map(|(left, right)| f(left, right))
             .collect();
-        Grid { v, cols: self.cols }
+        Ok(Grid { v, cols: self.cols })
+    }
+
+    /// Multiplies every entry by `s`.
+    pub fn scale<S>(&self, s: S) -> Grid<Prod<T, S>>
+    where
+        T: Copy + Mul<S>,
+        S: Copy,
+    {
+        self.map(|&item| item * s)
+    }
+
+    /// Divides every entry by `s`.
+    pub fn unscale<S>(&self, s: S) -> Grid<Quot<T, S>>
+    where
+        T: Copy + Div<S>,
+        S: Copy,
+    {
+        self.map(|&item| item / s)
+    }
+
+    pub fn count<F>(&self, pred: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.v.iter().filter(|item| pred(item)).count()
+    }
+
+    /// Folds every entry in row-major order.
+    pub fn fold<B, F>(&self, init: B, f: F) -> B
+    where
+        F: FnMut(B, &T) -> B,
+    {
+        self.v.iter().fold(init, f)
+    }
+}
+
+impl<T: Copy + Default + AddSelf> Grid<T> {
+    pub fn sum(&self) -> T {
+        self.fold(T::default(), |acc, &item| acc + item)
+    }
+}
+
+impl<T: PartialOrd> Grid<T> {
+    /// Returns `(row, col)` of the largest entry, or `None` if the grid is empty.
+    /// The first one in row-major order wins a tie.
+    pub fn argmax(&self) -> Option<(usize, usize)> {
+        self.iter_indexed()
+            .fold(None, |acc: Option<(usize, usize, &T)>, item| match acc {
+                Some(max) if *max.2 >= *item.2 => Some(max),
+                _ => Some(item),
+            })
+            .map(|(y, x, _)| (y, x))
+    }
+}
+
+impl<T: Copy + PartialOrd> Grid<T> {
+    /// Returns the smallest entry, or `None` if the grid is empty.
+    pub fn min(&self) -> Option<T> {
+        self.fold(None, |acc, &item| match acc {
+            Some(min) if min <= item => Some(min),
+            _ => Some(item),
+        })
+    }
+
+    /// Returns the largest entry, or `None` if the grid is empty.
+    pub fn max(&self) -> Option<T> {
+        self.fold(None, |acc, &item| match acc {
+            Some(max) if max >= item => Some(max),
+            _ => Some(item),
+        })
+    }
+}
+
+impl<T: Copy + AddSelf + MulScalar<f64>> Grid<T> {
+    /// Resamples the grid to `new_rows` x `new_cols` by bilinear interpolation.
+    /// The corner entries of the original and resampled grids coincide.
+    pub fn resample_bilinear(&self, new_rows: usize, new_cols: usize) -> Grid<T> {
+        let (rows, cols) = self.size();
+        let ys = resample_positions(rows, new_rows);
+        let xs = resample_positions(cols, new_cols);
+
+        let v = ys
+            .iter()
+            .flat_map(|&(y0, y1, ty)| {
+                xs.iter().map(move |&(x0, x1, tx)| {
+                    let top = self[y0][x0] * (1.0 - tx) + self[y0][x1] * tx;
+                    let bottom = self[y1][x0] * (1.0 - tx) + self[y1][x1] * tx;
+                    top * (1.0 - ty) + bottom * ty
+                })
+            })
+            .collect();
+        Grid::from_vec(v, new_cols)
+    }
+}
+
+/// For each of `new_len` resampled positions, returns the two neighboring original indices and the weight of the latter.
+fn resample_positions(len: usize, new_len: usize) -> Vec<(usize, usize, f64)> {
+    (0..new_len)
+        .map(|i| {
+            if len == 1 || new_len == 1 {
+                return (0, 0, 0.0);
+            }
+            let position = i as f64 * (len - 1) as f64 / (new_len - 1) as f64;
+            let lower = (position.floor() as usize).min(len - 2);
+            (lower, lower + 1, position - lower as f64)
+        })
+        .collect()
+}
+
+impl<T: Copy + Default + AddSelf + SubSelf + Dimensioned<Value = f64>> Grid<T> {
+    /// Same as `sum`, but with Kahan compensation for grids with many entries.
+    pub fn compensated_sum(&self) -> T {
+        utils::compensated_sum(self.v.iter().copied())
+    }
+}
+
+impl<T: Dimensioned<Value = f64>> Grid<T> {
+    /// Returns an error pointing at the first NaN or infinite entry, if any.
+    pub fn check_finite(&self) -> Result<(), ContrustError> {
+        match self
+            .v
+            .iter()
+            .position(|item| !item.value_unsafe().is_finite())
+        {
+            Some(i) => Err(ContrustError::NonFinite {
+                row: i / self.cols,
+                col: i % self.cols,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns `true` if both grids have the same size and every pair of entries differs by at most `tol`.
+    pub fn approx_eq(&self, other: &Grid<T>, tol: f64) -> bool {
+        self.size() == other.size()
+            && self
+                .v
+                .iter()
+                .zip(other.v.iter())
+                .all(|(left, right)| (left.value_unsafe() - right.value_unsafe()).abs() <= tol)
     }
 }
 
@@ -93,6 +321,260 @@ impl<T: Clone + Default> Grid<T> {
     }
 }
 
+impl<T: Clone> Grid<T> {
+    /// Returns a grid grown by `border` entries on every side.
+    pub fn pad(&self, border: usize, mode: PadMode<T>) -> Grid<T> {
+        let (rows, cols) = self.size();
+        let padded_cols = cols + 2 * border;
+        let v = (0..rows + 2 * border)
+            .flat_map(|y| (0..padded_cols).map(move |x| (y, x)))
+            .map(|(y, x)| {
+                let y = y as isize - border as isize;
+                let x = x as isize - border as isize;
+                match (pad_index(y, rows, &mode), pad_index(x, cols, &mode)) {
+                    (Some(y), Some(x)) => self[y][x].clone(),
+                    _ => match &mode {
+                        PadMode::Constant(value) => value.clone(),
+                        _ => unreachable!(),
+                    },
+                }
+            })
+            .collect();
+
+        Grid::from_vec(v, padded_cols)
+    }
+
+    /// Returns a copy of the `rows` x `cols` block whose top-left entry is `(row0, col0)`.
+    pub fn subgrid(&self, row0: usize, col0: usize, rows: usize, cols: usize) -> Grid<T> {
+        assert!(row0 + rows <= self.rows());
+        assert!(col0 + cols <= self.cols());
+
+        let v = (row0..row0 + rows)
+            .flat_map(|y| self[y][col0..col0 + cols].iter().cloned())
+            .collect();
+        Grid::from_vec(v, cols)
+    }
+
+    /// Places `other` to the right of `self`.
+    pub fn hconcat(&self, other: &Grid<T>) -> Grid<T> {
+        assert_eq!(
+            self.rows(),
+            other.rows(),
+            "horizontally concatenated grids must have the same number of rows"
+        );
+
+        let v = (0..self.rows())
+            .flat_map(|y| self[y].iter().chain(other[y].iter()).cloned())
+            .collect();
+        Grid::from_vec(v, self.cols() + other.cols())
+    }
+
+    /// Places `other` below `self`.
+    pub fn vconcat(&self, other: &Grid<T>) -> Grid<T> {
+        assert_eq!(
+            self.cols(),
+            other.cols(),
+            "vertically concatenated grids must have the same number of columns"
+        );
+
+        let v = self.v.iter().chain(other.v.iter()).cloned().collect();
+        Grid::from_vec(v, self.cols())
+    }
+
+    pub fn row_vec(&self, row: usize) -> Vec<T> {
+        assert!(row < self.rows());
+        self[row].to_vec()
+    }
+
+    pub fn col_vec(&self, col: usize) -> Vec<T> {
+        assert!(col < self.cols());
+        (0..self.rows()).map(|y| self[y][col].clone()).collect()
+    }
+
+    /// Returns the entries from the upper-left to the lower-right corner of a square grid.
+    pub fn diagonal(&self) -> Vec<T> {
+        assert_eq!(self.rows(), self.cols(), "diagonal requires a square grid");
+        (0..self.rows()).map(|i| self[i][i].clone()).collect()
+    }
+
+    /// Returns the entries from the upper-right to the lower-left corner of a square grid.
+    pub fn anti_diagonal(&self) -> Vec<T> {
+        assert_eq!(
+            self.rows(),
+            self.cols(),
+            "anti_diagonal requires a square grid"
+        );
+        let last = self.cols() - 1;
+        (0..self.rows())
+            .map(|i| self[i][last - i].clone())
+            .collect()
+    }
+
+    /// Returns the outer ring of entries clockwise from the upper-left corner:
+    /// the top row, the right column, the bottom row reversed and the left column reversed.
+    pub fn border(&self) -> Vec<T> {
+        let (rows, cols) = self.size();
+        if rows == 0 {
+            return Vec::new();
+        }
+
+        let top = (0..cols).map(|x| (0, x));
+        let right = (1..rows).map(|y| (y, cols - 1));
+        let bottom = if rows > 1 { 0..cols - 1 } else { 0..0 }
+            .rev()
+            .map(|x| (rows - 1, x));
+        let left = if cols > 1 { 1..rows - 1 } else { 0..0 }
+            .rev()
+            .map(|y| (y, 0));
+
+        top.chain(right)
+            .chain(bottom)
+            .chain(left)
+            .map(|(y, x)| self[y][x].clone())
+            .collect()
+    }
+
+    /// Returns the entries as row-major nested vectors.
+    pub fn to_nested_vec(&self) -> Vec<Vec<T>> {
+        (0..self.rows()).map(|y| self[y].to_vec()).collect()
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    pub fn rotate90(&self) -> Grid<T> {
+        let (rows, cols) = self.size();
+        let v = (0..cols)
+            .flat_map(|x| (0..rows).rev().map(move |y| self[y][x].clone()))
+            .collect();
+        Grid::from_vec(v, rows)
+    }
+
+    /// Mirrors the grid left to right.
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        let v = (0..self.rows())
+            .flat_map(|y| self[y].iter().rev().cloned())
+            .collect();
+        Grid::from_vec(v, self.cols())
+    }
+
+    /// Mirrors the grid top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T> {
+        let v = (0..self.rows())
+            .rev()
+            .flat_map(|y| self[y].iter().cloned())
+            .collect();
+        Grid::from_vec(v, self.cols())
+    }
+
+    /// Applies an odd-sized `kernel` centered on each entry, filling the outside of the grid according to `boundary`.
+    ///
+    /// The kernel is not flipped: `kernel[0][0]` weights the upper-left neighbor.
+    pub fn convolve<U, V>(&self, kernel: &Grid<U>, boundary: PadMode<T>) -> Grid<V>
+    where
+        T: Copy + Mul<U, Output = V>,
+        U: Copy,
+        V: AddSelf,
+    {
+        let (kernel_rows, kernel_cols) = kernel.size();
+        assert!(kernel_rows % 2 == 1 && kernel_cols % 2 == 1);
+
+        let (radius_y, radius_x) = (kernel_rows / 2, kernel_cols / 2);
+        let border = radius_y.max(radius_x);
+        let p = self.pad(border, boundary);
+
+        let (rows, cols) = self.size();
+        let v = (0..rows)
+            .flat_map(|y| (0..cols).map(move |x| (y, x)))
+            .map(|(y, x)| {
+                let top = y + border - radius_y;
+                let left = x + border - radius_x;
+                (0..kernel_rows)
+                    .flat_map(|ky| (0..kernel_cols).map(move |kx| (ky, kx)))
+                    .map(|(ky, kx)| p[top + ky][left + kx] * kernel[ky][kx])
+                    .reduce(|acc, item| acc + item)
+                    .unwrap()
+            })
+            .collect();
+        Grid::from_vec(v, cols)
+    }
+
+    /// Smooths the grid with a Gaussian kernel of standard deviation `sigma` entries, truncated at `3 * sigma`.
+    ///
+    /// The kernel is normalized, so the total is kept as long as `boundary` does not cut off the spread.
+    pub fn smooth_gaussian(&self, sigma: f64, boundary: PadMode<T>) -> Grid<T>
+    where
+        T: Copy + AddSelf + MulScalar<f64>,
+    {
+        assert!(sigma > 0.0);
+
+        let radius = (3.0 * sigma).ceil() as isize;
+        let weights = (-radius..=radius)
+            .map(|i| (-(i * i) as f64 / (2.0 * sigma * sigma)).exp())
+            .collect::<Vec<_>>();
+        let total = weights.iter().sum::<f64>();
+        let weights = weights.into_iter().map(|w| w / total).collect::<Vec<_>>();
+
+        // 行方向と列方向に分けて畳み込む
+        let len = weights.len();
+        let row_kernel = Grid::from_vec(weights.clone(), len);
+        let col_kernel = Grid::from_vec(weights, 1);
+        self.convolve(&row_kernel, boundary)
+            .convolve(&col_kernel, boundary)
+    }
+}
+
+/// How `Grid::pad` fills the entries outside the original grid.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PadMode<T> {
+    /// Fills the border with the given value.
+    Constant(T),
+    /// Repeats the nearest edge entry.
+    Replicate,
+    /// Mirrors the entries about the edge, not repeating the edge itself.
+    Reflect,
+}
+
+/// Maps a possibly out-of-range index into `0..len`, or `None` if the entry comes from the constant border.
+fn pad_index<T>(index: isize, len: usize, mode: &PadMode<T>) -> Option<usize> {
+    let last = len as isize - 1;
+    match mode {
+        PadMode::Constant(_) if index < 0 || index > last => None,
+        PadMode::Constant(_) => Some(index as usize),
+        PadMode::Replicate => Some(index.max(0).min(last) as usize),
+        PadMode::Reflect if last == 0 => Some(0),
+        PadMode::Reflect => {
+            let period = 2 * last;
+            let i = index.rem_euclid(period);
+            Some(if i > last { period - i } else { i } as usize)
+        }
+    }
+}
+
+/// Prints the entries as a right-aligned table, one row per line.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let texts = self.v.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let width = texts
+            .iter()
+            .map(|text| text.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        for (y, row) in texts.chunks(self.cols).enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for (x, text) in row.iter().enumerate() {
+                if x > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{:>width$}", text, width = width)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl<T> Index<usize> for Grid<T> {
     type Output = [T];
 
@@ -167,6 +649,7 @@ impl<'g, T> Index<usize> for Col<'g, T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use dimensioned::si::{Hertz, Meter, MeterPerSecond, Second, M};
 
     /// ↓row →col
     ///
@@ -198,6 +681,147 @@ mod tests {
     //     assert_eq!(sample_grid(), g);
     // }
 
+    #[test]
+    fn test_try_from_vec() {
+        let g = Grid::try_from_vec(vec![0, 1, 2, 3, 4, 5], 3);
+        assert_eq!(Ok(sample_grid()), g);
+    }
+
+    #[test]
+    fn test_try_from_vec_invalid_length() {
+        let g = Grid::try_from_vec(vec![0, 1, 2, 3, 4], 3);
+        assert_eq!(Err(ContrustError::InvalidGridLength { len: 5, cols: 3 }), g);
+
+        let g = Grid::try_from_vec(vec![0, 1, 2], 0);
+        assert_eq!(Err(ContrustError::InvalidGridLength { len: 3, cols: 0 }), g);
+    }
+
+    #[test]
+    fn test_row_vec() {
+        let g = Grid::from_vec((0..9).collect(), 3);
+        assert_eq!(vec![3, 4, 5], g.row_vec(1));
+    }
+
+    #[test]
+    fn test_col_vec() {
+        let g = Grid::from_vec((0..9).collect(), 3);
+        assert_eq!(vec![1, 4, 7], g.col_vec(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_row_vec_out_of_bounds() {
+        sample_grid().row_vec(2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_col_vec_out_of_bounds() {
+        sample_grid().col_vec(3);
+    }
+
+    #[test]
+    fn test_diagonal() {
+        let g = Grid::from_vec((0..9).collect(), 3);
+        assert_eq!(vec![0, 4, 8], g.diagonal());
+    }
+
+    #[test]
+    fn test_anti_diagonal() {
+        let g = Grid::from_vec((0..9).collect(), 3);
+        assert_eq!(vec![2, 4, 6], g.anti_diagonal());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_diagonal_non_square() {
+        sample_grid().diagonal();
+    }
+
+    #[test]
+    fn test_border() {
+        let g = Grid::from_vec((0..9).collect(), 3);
+        assert_eq!(vec![0, 1, 2, 5, 8, 7, 6, 3], g.border());
+
+        assert_eq!(vec![0, 1, 2, 5, 4, 3], sample_grid().border());
+        assert_eq!(vec![0, 1, 2], Grid::from_vec(vec![0, 1, 2], 3).border());
+        assert_eq!(vec![0, 1, 2], Grid::from_vec(vec![0, 1, 2], 1).border());
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidGridLength")]
+    fn test_from_vec_zero_cols() {
+        Grid::from_vec(vec![0, 1, 2], 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidGridLength")]
+    fn test_subgrid_zero_cols() {
+        sample_grid().subgrid(0, 0, 1, 0);
+    }
+
+    #[test]
+    fn test_nested_vec_round_trip() {
+        let nested = sample_grid().to_nested_vec();
+        assert_eq!(vec![vec![0, 1, 2], vec![3, 4, 5]], nested);
+
+        let g = Grid::from_nested_vec(nested);
+        assert_eq!(sample_grid(), g);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_nested_vec_ragged() {
+        Grid::from_nested_vec(vec![vec![0, 1, 2], vec![3, 4]]);
+    }
+
+    #[test]
+    fn test_try_from_nested_vec() {
+        let g = Grid::try_from_nested_vec(vec![vec![0, 1, 2], vec![3, 4, 5]]);
+        assert_eq!(Ok(sample_grid()), g);
+
+        let g = Grid::<i32>::try_from_nested_vec(vec![]);
+        assert_eq!(Err(ContrustError::EmptyInput), g);
+
+        let g = Grid::try_from_nested_vec(vec![vec![0, 1, 2], vec![3, 4]]);
+        let expected = ContrustError::DimensionMismatch {
+            expected: (1, 3),
+            actual: (1, 2),
+        };
+        assert_eq!(Err(expected), g);
+    }
+
+    #[test]
+    fn test_as_slice() {
+        let mut g = sample_grid();
+        assert_eq!(&[0, 1, 2, 3, 4, 5], g.as_slice());
+
+        g.as_mut_slice()[4] = 9;
+        assert_eq!(9, g[1][1]);
+    }
+
+    #[test]
+    fn test_reshape() {
+        let g = sample_grid().reshape(2);
+
+        assert_eq!((3, 2), g.size());
+        assert_eq!(&[0, 1], &g[0]);
+        assert_eq!(&[2, 3], &g[1]);
+        assert_eq!(&[4, 5], &g[2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidGridLength")]
+    fn test_reshape_invalid() {
+        sample_grid().reshape(4);
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidGridLength")]
+    fn test_reshape_zero_cols() {
+        sample_grid().reshape(0);
+    }
+
     #[test]
     fn test_rows() {
         assert_eq!(2, sample_grid().rows());
@@ -249,6 +873,356 @@ mod tests {
         assert!(c.next().is_none())
     }
 
+    #[test]
+    fn test_iter_indexed() {
+        let g = sample_grid();
+        let indexed = g.iter_indexed().collect::<Vec<_>>();
+
+        assert_eq!(6, indexed.len());
+        assert_eq!((0, 0, &0), indexed[0]);
+        assert_eq!((0, 2, &2), indexed[2]);
+        assert_eq!((1, 0, &3), indexed[3]);
+        assert_eq!((1, 2, &5), indexed[5]);
+    }
+
+    #[test]
+    fn test_iter_rows_mut() {
+        let mut g = sample_grid();
+        for row in g.iter_rows_mut() {
+            for item in row.iter_mut() {
+                *item = -*item;
+            }
+        }
+
+        assert_eq!(0, g[0][0]);
+        assert_eq!(-1, g[0][1]);
+        assert_eq!(-2, g[0][2]);
+        assert_eq!(-3, g[1][0]);
+        assert_eq!(-4, g[1][1]);
+        assert_eq!(-5, g[1][2]);
+    }
+
+    #[test]
+    fn test_pad_constant() {
+        let g = sample_grid().pad(1, PadMode::Constant(9));
+
+        #[rustfmt::skip]
+        let expected = Grid::from_vec(vec![
+            9, 9, 9, 9, 9,
+            9, 0, 1, 2, 9,
+            9, 3, 4, 5, 9,
+            9, 9, 9, 9, 9,
+        ], 5);
+        assert_eq!(expected, g);
+    }
+
+    #[test]
+    fn test_pad_replicate() {
+        let g = sample_grid().pad(1, PadMode::Replicate);
+
+        #[rustfmt::skip]
+        let expected = Grid::from_vec(vec![
+            0, 0, 1, 2, 2,
+            0, 0, 1, 2, 2,
+            3, 3, 4, 5, 5,
+            3, 3, 4, 5, 5,
+        ], 5);
+        assert_eq!(expected, g);
+    }
+
+    #[test]
+    fn test_pad_reflect() {
+        let g = sample_grid().pad(1, PadMode::Reflect);
+
+        #[rustfmt::skip]
+        let expected = Grid::from_vec(vec![
+            4, 3, 4, 5, 4,
+            1, 0, 1, 2, 1,
+            4, 3, 4, 5, 4,
+            1, 0, 1, 2, 1,
+        ], 5);
+        assert_eq!(expected, g);
+    }
+
+    #[test]
+    fn test_subgrid() {
+        let g = Grid::from_vec((0..16).collect(), 4);
+        let sub = g.subgrid(1, 1, 2, 2);
+
+        assert_eq!((2, 2), sub.size());
+        assert_eq!(5, sub[0][0]);
+        assert_eq!(6, sub[0][1]);
+        assert_eq!(9, sub[1][0]);
+        assert_eq!(10, sub[1][1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_subgrid_out_of_bounds() {
+        sample_grid().subgrid(1, 1, 2, 2);
+    }
+
+    #[test]
+    fn test_hconcat() {
+        let g = sample_grid().hconcat(&sample_grid());
+
+        assert_eq!((2, 6), g.size());
+        assert_eq!(&[0, 1, 2, 0, 1, 2], &g[0]);
+        assert_eq!(&[3, 4, 5, 3, 4, 5], &g[1]);
+    }
+
+    #[test]
+    fn test_vconcat() {
+        let g = sample_grid().vconcat(&sample_grid());
+
+        assert_eq!((4, 3), g.size());
+        assert_eq!(&[0, 1, 2], &g[0]);
+        assert_eq!(&[3, 4, 5], &g[1]);
+        assert_eq!(&[0, 1, 2], &g[2]);
+        assert_eq!(&[3, 4, 5], &g[3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_hconcat_mismatch() {
+        sample_grid().hconcat(&Grid::fill_default(3, 3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_vconcat_mismatch() {
+        sample_grid().vconcat(&Grid::fill_default(2, 2));
+    }
+
+    #[test]
+    fn test_scale() {
+        let g = Grid::from_vec(vec![1.0 * M, 2.0 * M, 3.0 * M, 4.0 * M], 2);
+        let scaled: Grid<MeterPerSecond<f64>> = g.scale(Hertz::new(2.0));
+
+        assert_eq!(g.size(), scaled.size());
+        assert_eq!(MeterPerSecond::new(2.0), scaled[0][0]);
+        assert_eq!(MeterPerSecond::new(4.0), scaled[0][1]);
+        assert_eq!(MeterPerSecond::new(6.0), scaled[1][0]);
+        assert_eq!(MeterPerSecond::new(8.0), scaled[1][1]);
+    }
+
+    #[test]
+    fn test_unscale() {
+        let g = Grid::from_vec(vec![1.0 * M, 2.0 * M, 3.0 * M, 4.0 * M], 2);
+        let unscaled: Grid<MeterPerSecond<f64>> = g.unscale(Second::new(2.0));
+
+        assert_eq!(g.size(), unscaled.size());
+        assert_eq!(MeterPerSecond::new(0.5), unscaled[0][0]);
+        assert_eq!(MeterPerSecond::new(1.0), unscaled[0][1]);
+        assert_eq!(MeterPerSecond::new(1.5), unscaled[1][0]);
+        assert_eq!(MeterPerSecond::new(2.0), unscaled[1][1]);
+    }
+
+    #[test]
+    fn test_fold() {
+        let product = sample_grid().fold(1, |acc, &i| acc * (i + 1));
+        assert_eq!(720, product);
+    }
+
+    #[test]
+    fn test_sum() {
+        assert_eq!(15, sample_grid().sum());
+    }
+
+    #[test]
+    fn test_sum_non_finite() {
+        let g = Grid::from_vec(vec![f64::INFINITY, 1.0], 2);
+        assert_eq!(f64::INFINITY, g.sum());
+
+        let g = Grid::from_vec(vec![f64::MAX, f64::MAX], 2);
+        assert_eq!(f64::INFINITY, g.sum());
+    }
+
+    #[test]
+    fn test_compensated_sum() {
+        let mut g = Grid::fill_default(1, 10001);
+        g[0][0] = 1.0 * M;
+        for x in 1..10001 {
+            g[0][x] = 1e-16 * M;
+        }
+        assert!((g.compensated_sum() - (1.0 + 1e-12) * M).value_unsafe.abs() < 1e-15);
+
+        let g = Grid::from_vec(vec![f64::INFINITY * M, 1.0 * M], 2);
+        assert_eq!(f64::INFINITY * M, g.compensated_sum());
+    }
+
+    #[test]
+    fn test_min() {
+        assert_eq!(Some(0), sample_grid().min());
+        assert_eq!(None, Grid::<i32>::from_vec(vec![], 3).min());
+    }
+
+    #[test]
+    fn test_max() {
+        assert_eq!(Some(5), sample_grid().max());
+        assert_eq!(None, Grid::<i32>::from_vec(vec![], 3).max());
+    }
+
+    #[test]
+    fn test_rotate90() {
+        let g = sample_grid().rotate90();
+
+        assert_eq!((3, 2), g.size());
+        assert_eq!(&[3, 0], &g[0]);
+        assert_eq!(&[4, 1], &g[1]);
+        assert_eq!(&[5, 2], &g[2]);
+    }
+
+    #[test]
+    fn test_flip_horizontal() {
+        let g = sample_grid().flip_horizontal();
+
+        assert_eq!((2, 3), g.size());
+        assert_eq!(&[2, 1, 0], &g[0]);
+        assert_eq!(&[5, 4, 3], &g[1]);
+    }
+
+    #[test]
+    fn test_flip_vertical() {
+        let g = sample_grid().flip_vertical();
+
+        assert_eq!((2, 3), g.size());
+        assert_eq!(&[3, 4, 5], &g[0]);
+        assert_eq!(&[0, 1, 2], &g[1]);
+    }
+
+    #[test]
+    fn test_resample_bilinear() {
+        // f = x + 2y
+        let g = Grid::from_vec(vec![0.0, 1.0, 2.0, 3.0], 2);
+        let r = g.resample_bilinear(4, 4);
+        assert_eq!((4, 4), r.size());
+
+        for y in 0..4 {
+            for x in 0..4 {
+                let expected = x as f64 / 3.0 + 2.0 * y as f64 / 3.0;
+                assert!((expected - r[y][x]).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_resample_bilinear_single_row() {
+        let g = Grid::from_vec(vec![0.0, 2.0], 2);
+        let r = g.resample_bilinear(2, 3);
+
+        assert_eq!(&[0.0, 1.0, 2.0], &r[0]);
+        assert_eq!(&[0.0, 1.0, 2.0], &r[1]);
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let g = Grid::from_vec(vec![0.1 * M, 0.2 * M, 0.3 * M, 0.4 * M], 2);
+        let near = Grid::from_vec(vec![0.1 * M, 0.2 * M, 0.3 * M, (0.4 + 1e-13) * M], 2);
+        let far = Grid::from_vec(vec![0.1 * M, 0.2 * M, 0.3 * M, 0.5 * M], 2);
+
+        assert!(g.approx_eq(&near, 1e-12));
+        assert!(!g.approx_eq(&far, 1e-12));
+        assert!(g.approx_eq(&far, 0.2));
+        assert!(!g.approx_eq(&Grid::<Meter<f64>>::fill_default(1, 4), 1.0));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("0 1 2\n3 4 5", sample_grid().to_string());
+
+        let g = Grid::from_vec(vec![1, -20, 300, 4], 2);
+        assert_eq!("  1 -20\n300   4", g.to_string());
+    }
+
+    #[test]
+    fn test_map() {
+        let g = sample_grid().map(|&i| i * i);
+
+        assert_eq!(g.size(), sample_grid().size());
+        assert_eq!(&[0, 1, 4], &g[0]);
+        assert_eq!(&[9, 16, 25], &g[1]);
+    }
+
+    #[test]
+    fn test_try_merge_entrywise() {
+        let g = sample_grid();
+
+        let merged = g.try_merge_entrywise(&g, |&l, &r| l + r);
+        assert_eq!(Ok(g.map(|&i| i * 2)), merged);
+
+        let merged = g.try_merge_entrywise(&Grid::<i32>::fill_default(3, 2), |&l, &r| l + r);
+        let expected = ContrustError::DimensionMismatch {
+            expected: (2, 3),
+            actual: (3, 2),
+        };
+        assert_eq!(Err(expected), merged);
+    }
+
+    #[test]
+    fn test_check_finite() {
+        let mut g = Grid::from_vec(vec![0.0 * M, 1.0 * M, 2.0 * M, 3.0 * M], 2);
+        assert_eq!(Ok(()), g.check_finite());
+
+        g[1][0] = f64::NAN * M;
+        assert_eq!(
+            Err(ContrustError::NonFinite { row: 1, col: 0 }),
+            g.check_finite()
+        );
+    }
+
+    #[test]
+    fn test_convolve() {
+        let kernel = Grid::from_vec(vec![0, 1, 0, 1, 1, 1, 0, 1, 0], 3);
+        let g = sample_grid().convolve(&kernel, PadMode::Constant(0));
+
+        #[rustfmt::skip]
+        let expected = Grid::from_vec(vec![
+            4, 7, 8,
+            7, 13, 11,
+        ], 3);
+        assert_eq!(expected, g);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_convolve_even_kernel() {
+        let kernel = Grid::from_vec(vec![1, 1], 2);
+        sample_grid().convolve(&kernel, PadMode::Replicate);
+    }
+
+    #[test]
+    fn test_smooth_gaussian() {
+        let mut g = Grid::fill_default(9, 9);
+        g[4][4] = 1.0;
+
+        let smoothed = g.smooth_gaussian(1.0, PadMode::Constant(0.0));
+        assert!((1.0 - smoothed.sum()).abs() < 1e-12);
+        assert_eq!(Some(smoothed[4][4]), smoothed.max());
+
+        // 中心から離れるほど小さく, 上下左右に対称
+        for d in 1..=3 {
+            assert!(smoothed[4][4 + d] < smoothed[4][4 + d - 1]);
+            assert!((smoothed[4][4 + d] - smoothed[4][4 - d]).abs() < 1e-15);
+            assert!((smoothed[4][4 + d] - smoothed[4 + d][4]).abs() < 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_count() {
+        assert_eq!(3, sample_grid().count(|&i| i > 2));
+        assert_eq!(0, sample_grid().count(|&i| i > 5));
+    }
+
+    #[test]
+    fn test_argmax() {
+        assert_eq!(Some((1, 2)), sample_grid().argmax());
+
+        let g = Grid::from_vec(vec![1, 7, 3, 7], 2);
+        assert_eq!(Some((0, 1)), g.argmax());
+        assert_eq!(None, Grid::<i32>::from_vec(vec![], 2).argmax());
+    }
+
     #[test]
     fn test_map_rowwise() {
         let g = sample_grid();
@@ -1,53 +1,144 @@
-use crate::grid::Grid;
+use crate::grid::{Grid, PadMode};
 use crate::op_alias::{AddSelf, DivScalar, MulScalar, SubSelf};
 use dimensioned::typenum::{Prod, Quot};
+use dimensioned::Sqrt;
 use std::iter::once;
 use std::ops::{Div, Mul};
 
+/// Type of the second derivative of `T` along a grid interval `U`.
+pub type SecondDiff<T, U> = Quot<T, Prod<U, U>>;
+
+/// `(f_xx, f_yy, f_xy)` returned by `calculate_hessian`.
+pub type Hessian<T, U> = (
+    Grid<SecondDiff<T, U>>,
+    Grid<SecondDiff<T, U>>,
+    Grid<SecondDiff<T, U>>,
+);
+
+/// Type of the fourth derivative of `T` along a grid interval `U`.
+pub type FourthDiff<T, U> = Quot<T, Prod<Prod<U, U>, Prod<U, U>>>;
+
+pub fn calculate_difference_1d<T, U>(data: &[T], delta: U) -> Vec<Quot<T, U>>
+where
+    T: Copy + SubSelf + Div<U> + DivScalar<f64>,
+    U: Copy,
+{
+    difference_with_edge(data.len(), |i| data[i], EdgeScheme::FirstOrder)
+        .into_iter()
+        .map(|diff| diff / delta)
+        .collect()
+}
+
 pub fn calculate_partial_difference_x<T, U>(grid: &Grid<T>, delta: U) -> Grid<Quot<T, U>>
 where
     T: Copy + SubSelf + Div<U> + DivScalar<f64>,
     U: Copy,
 {
+    calculate_partial_difference_x_with_edge(grid, delta, EdgeScheme::FirstOrder)
+}
+
+pub fn calculate_partial_difference_y<T, U>(grid: &Grid<T>, delta: U) -> Grid<Quot<T, U>>
+where
+    T: Copy + SubSelf + Div<U> + DivScalar<f64>,
+    U: Copy,
+{
+    calculate_partial_difference_y_with_edge(grid, delta, EdgeScheme::FirstOrder)
+}
+
+/// Differentiates along x using the difference on the upwind side of `velocity_grid`.
+pub fn calculate_upwind_x<T, U, V>(
+    grid: &Grid<T>,
+    delta: U,
+    velocity_grid: &Grid<V>,
+) -> Grid<Quot<T, U>>
+where
+    T: Copy + SubSelf + Div<U>,
+    U: Copy,
+    V: Copy + Default + PartialOrd,
+{
+    assert_eq!(grid.size(), velocity_grid.size());
+
     grid.map_rowwise(|row| {
         let cols = row.cols();
-        // 端の微分は一次精度
-        let first = row[1] - row[0];
-        let last = row[cols - 1] - row[cols - 2];
-        // 端以外の微分は二次精度
-        let inner = (1..=cols - 2)
-            .map(move |x| row[x + 1] - row[x - 1])
-            .map(move |diff| diff / 2.0);
+        let velocity = &velocity_grid[row.row()];
+        (0..cols).map(move |x| {
+            // 正の速度なら後退差分, それ以外は前進差分. 片側が無い端では反対側を使う
+            let backward = x > 0 && (velocity[x] > V::default() || x == cols - 1);
+            let diff = if backward {
+                row[x] - row[x - 1]
+            } else {
+                row[x + 1] - row[x]
+            };
+            diff / delta
+        })
+    })
+}
 
-        once(first)
-            .chain(inner)
-            .chain(once(last))
+/// Scheme used for the first derivative at the edges of a grid.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum EdgeScheme {
+    /// One-sided first-order difference `f[1] - f[0]`.
+    #[default]
+    FirstOrder,
+    /// One-sided second-order difference `(-3f[0] + 4f[1] - f[2]) / 2`. Needs at least 3 entries.
+    SecondOrder,
+}
+
+pub fn calculate_partial_difference_x_with_edge<T, U>(
+    grid: &Grid<T>,
+    delta: U,
+    edge: EdgeScheme,
+) -> Grid<Quot<T, U>>
+where
+    T: Copy + SubSelf + Div<U> + DivScalar<f64>,
+    U: Copy,
+{
+    grid.map_rowwise(|row| {
+        difference_with_edge(row.cols(), |x| row[x], edge)
+            .into_iter()
             .map(|diff| diff / delta)
     })
 }
 
-pub fn calculate_partial_difference_y<T, U>(grid: &Grid<T>, delta: U) -> Grid<Quot<T, U>>
+pub fn calculate_partial_difference_y_with_edge<T, U>(
+    grid: &Grid<T>,
+    delta: U,
+    edge: EdgeScheme,
+) -> Grid<Quot<T, U>>
 where
     T: Copy + SubSelf + Div<U> + DivScalar<f64>,
     U: Copy,
 {
     grid.map_colwise(|col| {
-        // 端の微分は一次精度
-        let rows = col.rows();
-        let first = col[1] - col[0];
-        let last = col[rows - 1] - col[rows - 2];
-        // 端以外の微分は二次精度
-        let inner = (1..=rows - 2)
-            .map(move |y| col[y + 1] - col[y - 1])
-            .map(move |diff| diff / 2.0);
-
-        once(first)
-            .chain(inner)
-            .chain(once(last))
+        difference_with_edge(col.rows(), |y| col[y], edge)
+            .into_iter()
             .map(|diff| diff / delta)
     })
 }
 
+/// Returns the differences of `len` entries given by `at`, not yet divided by the grid interval.
+fn difference_with_edge<T, F>(len: usize, at: F, edge: EdgeScheme) -> Vec<T>
+where
+    T: Copy + SubSelf + DivScalar<f64>,
+    F: Fn(usize) -> T,
+{
+    let first = at(1) - at(0);
+    let last = at(len - 1) - at(len - 2);
+    let (first, last) = match edge {
+        // 端の微分は一次精度
+        EdgeScheme::FirstOrder => (first, last),
+        // 隣の差分で一次の誤差を打ち消す
+        EdgeScheme::SecondOrder => (
+            first - (at(2) - at(1) - first) / 2.0,
+            last - (at(len - 2) - at(len - 3) - last) / 2.0,
+        ),
+    };
+    // 端以外の微分は二次精度
+    let inner = (1..=len - 2).map(|i| (at(i + 1) - at(i - 1)) / 2.0);
+
+    once(first).chain(inner).chain(once(last)).collect()
+}
+
 pub fn calculate_partial_difference_xx<T, U>(grid: &Grid<T>, delta: U) -> Grid<Quot<T, Prod<U, U>>>
 where
     T: Copy + AddSelf + SubSelf + Div<Prod<U, U>> + MulScalar<f64>,
@@ -88,6 +179,35 @@ where
     Grid::from_vec(grid_vec, cols)
 }
 
+pub fn calculate_partial_difference_xy<T, U>(grid: &Grid<T>, delta: U) -> Grid<Quot<T, Prod<U, U>>>
+where
+    T: Copy + AddSelf + SubSelf + Div<Prod<U, U>> + DivScalar<f64>,
+    U: Copy + Mul<U>,
+{
+    let (rows, cols) = grid.size();
+    // 端は片側差分(一次精度), 端以外は中心差分(二次精度)
+    let neighbors = |i: usize, len: usize| {
+        if i == 0 {
+            (0, 1, 1.0)
+        } else if i == len - 1 {
+            (len - 2, len - 1, 1.0)
+        } else {
+            (i - 1, i + 1, 2.0)
+        }
+    };
+
+    let grid_vec = (0..rows)
+        .flat_map(|y| (0..cols).map(move |x| (y, x)))
+        .map(|(y, x)| {
+            let (y0, y1, y_span) = neighbors(y, rows);
+            let (x0, x1, x_span) = neighbors(x, cols);
+            let diff = grid[y1][x1] - grid[y0][x1] - grid[y1][x0] + grid[y0][x0];
+            diff / (x_span * y_span) / (delta * delta)
+        })
+        .collect();
+    Grid::from_vec(grid_vec, cols)
+}
+
 pub fn calculate_nabla<T, U>(grid: &Grid<T>, delta: U) -> (Grid<Quot<T, U>>, Grid<Quot<T, U>>)
 where
     T: Copy + SubSelf + Div<U> + DivScalar<f64>,
@@ -98,6 +218,29 @@ where
     (x, y)
 }
 
+/// Returns `(∂f/∂x, ∂f/∂y)` for each entry.
+pub fn calculate_gradient<T, U>(grid: &Grid<T>, delta: U) -> Grid<(Quot<T, U>, Quot<T, U>)>
+where
+    T: Copy + SubSelf + Div<U> + DivScalar<f64>,
+    U: Copy,
+    Quot<T, U>: Copy,
+{
+    let (x, y) = calculate_nabla(grid, delta);
+    x.merge_entrywise(&y, |&x, &y| (x, y))
+}
+
+/// Returns `|∇f|` for each entry.
+pub fn calculate_gradient_magnitude<T, U>(grid: &Grid<T>, delta: U) -> Grid<Quot<T, U>>
+where
+    T: Copy + SubSelf + Div<U> + DivScalar<f64>,
+    U: Copy,
+    Quot<T, U>: Copy + Mul<Quot<T, U>>,
+    Prod<Quot<T, U>, Quot<T, U>>: AddSelf + Sqrt<Output = Quot<T, U>>,
+{
+    let (x, y) = calculate_nabla(grid, delta);
+    x.merge_entrywise(&y, |&x, &y| (x * x + y * y).sqrt())
+}
+
 pub fn calculate_laplacian<T, U>(grid: &Grid<T>, delta: U) -> Grid<Quot<T, Prod<U, U>>>
 where
     T: Copy + AddSelf + SubSelf + Div<Prod<U, U>> + MulScalar<f64>,
@@ -110,6 +253,63 @@ where
     xx.merge_entrywise(&yy, |&x, &y| x + y)
 }
 
+/// Returns `(f_xx, f_yy, f_xy)`.
+pub fn calculate_hessian<T, U>(grid: &Grid<T>, delta: U) -> Hessian<T, U>
+where
+    T: Copy + AddSelf + SubSelf + Div<Prod<U, U>> + MulScalar<f64> + DivScalar<f64>,
+    U: Copy + Mul<U>,
+{
+    let xx = calculate_partial_difference_xx(grid, delta);
+    let yy = calculate_partial_difference_yy(grid, delta);
+    let xy = calculate_partial_difference_xy(grid, delta);
+    (xx, yy, xy)
+}
+
+/// Applies `calculate_laplacian` to each component of a vector field.
+pub fn calculate_vector_laplacian<T, U>(
+    grid: &Grid<(T, T)>,
+    delta: U,
+) -> Grid<(SecondDiff<T, U>, SecondDiff<T, U>)>
+where
+    T: Copy + AddSelf + SubSelf + Div<Prod<U, U>> + MulScalar<f64>,
+    U: Copy + Mul<U>,
+    SecondDiff<T, U>: Copy + AddSelf,
+{
+    let x = calculate_laplacian(&grid.map(|&(x, _)| x), delta);
+    let y = calculate_laplacian(&grid.map(|&(_, y)| y), delta);
+    x.merge_entrywise(&y, |&x, &y| (x, y))
+}
+
+/// Returns `∇⁴f` by the 13-point stencil.
+///
+/// The stencil reaches two entries away, so the grid is extended beyond its edges by replicating the edge entries.
+/// Only entries at least two cells away from every edge are accurate;
+/// the outer two rings describe the field as if it were flat outside the grid.
+pub fn calculate_biharmonic<T, U>(grid: &Grid<T>, delta: U) -> Grid<FourthDiff<T, U>>
+where
+    T: Copy + AddSelf + SubSelf + Div<Prod<Prod<U, U>, Prod<U, U>>> + MulScalar<f64>,
+    U: Copy + Mul<U>,
+    Prod<U, U>: Copy + Mul<Prod<U, U>>,
+{
+    let (rows, cols) = grid.size();
+    // 端の外側は端の値で延長する
+    let p = grid.pad(2, PadMode::Replicate);
+
+    // 13点ステンシル
+    let grid_vec = (2..rows + 2)
+        .flat_map(|y| (2..cols + 2).map(move |x| (y, x)))
+        .map(|(y, x)| {
+            let center = p[y][x] * 20.0;
+            let adjacent = (p[y - 1][x] + p[y + 1][x] + p[y][x - 1] + p[y][x + 1]) * 8.0;
+            let diagonal =
+                (p[y - 1][x - 1] + p[y - 1][x + 1] + p[y + 1][x - 1] + p[y + 1][x + 1]) * 2.0;
+            let far = p[y - 2][x] + p[y + 2][x] + p[y][x - 2] + p[y][x + 2];
+            (center - adjacent + diagonal + far) / ((delta * delta) * (delta * delta))
+        })
+        .collect();
+    Grid::from_vec(grid_vec, cols)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,6 +337,15 @@ mod tests {
         g
     }
 
+    #[test]
+    fn test_calculate_difference_1d() {
+        let g = sample_grid();
+        let delta = Meter::new(10.0);
+
+        let d = calculate_difference_1d(&g[0], delta);
+        assert_eq!(&calculate_partial_difference_x(&g, delta)[0], d.as_slice());
+    }
+
     #[test]
     fn test_calculate_partial_difference_x() {
         let g = sample_grid();
@@ -175,6 +384,59 @@ mod tests {
         assert_eq!(Unitless::new(0.4), d[2][2]);
     }
 
+    #[test]
+    fn test_calculate_upwind_x() {
+        let g = Grid::from_vec(
+            vec![0.0 * M, 0.0 * M, 0.0 * M, 1.0 * M, 1.0 * M, 1.0 * M],
+            6,
+        );
+        let velocity = Grid::from_vec(vec![1.0; 6], 6);
+        let delta = Meter::new(1.0);
+        // クーラン数1で1ステップ移流させる
+        let shift = Meter::new(1.0);
+
+        let upwind = calculate_upwind_x(&g, delta, &velocity);
+        let advected = g.merge_entrywise(&upwind, |&f, &d| f - d * shift);
+        assert_eq!(0.0 * M, advected[0][2]);
+        assert_eq!(0.0 * M, advected[0][3]);
+        assert_eq!(1.0 * M, advected[0][4]);
+        for x in 0..6 {
+            assert!(advected[0][x] >= 0.0 * M && advected[0][x] <= 1.0 * M);
+        }
+
+        let central = calculate_partial_difference_x(&g, delta);
+        let advected = g.merge_entrywise(&central, |&f, &d| f - d * shift);
+        assert!(advected[0][2] < 0.0 * M);
+    }
+
+    #[test]
+    fn test_calculate_partial_difference_with_edge() {
+        // f = x^2 + y^2
+        let mut g = Grid::fill_default(4, 4);
+        for y in 0..4 {
+            for x in 0..4 {
+                g[y][x] = ((x * x + y * y) as f64) * M;
+            }
+        }
+        let delta = Meter::new(1.0);
+
+        let x_first = calculate_partial_difference_x_with_edge(&g, delta, EdgeScheme::FirstOrder);
+        let y_first = calculate_partial_difference_y_with_edge(&g, delta, EdgeScheme::FirstOrder);
+        assert_eq!(calculate_partial_difference_x(&g, delta), x_first);
+        assert_eq!(calculate_partial_difference_y(&g, delta), y_first);
+        assert_eq!(Unitless::new(1.0), x_first[0][0]);
+        assert_eq!(Unitless::new(5.0), x_first[0][3]);
+
+        let x_second = calculate_partial_difference_x_with_edge(&g, delta, EdgeScheme::SecondOrder);
+        let y_second = calculate_partial_difference_y_with_edge(&g, delta, EdgeScheme::SecondOrder);
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(Unitless::new(2.0 * j as f64), x_second[i][j]);
+                assert_eq!(Unitless::new(2.0 * i as f64), y_second[i][j]);
+            }
+        }
+    }
+
     #[test]
     fn test_calculate_partial_difference_xx() {
         let g = sample_grid();
@@ -213,6 +475,22 @@ mod tests {
         assert_eq!(PerMeter::new(0.04), d[2][2]);
     }
 
+    #[test]
+    fn test_calculate_partial_difference_xy() {
+        let g = sample_grid();
+        let delta = Meter::new(10.0);
+
+        let d = calculate_partial_difference_xy(&g, delta);
+        assert_eq!(g.size(), d.size());
+
+        // sample_grid は x と y について線形で, 混合項を持たない
+        for y in 0..3 {
+            for x in 0..3 {
+                assert_eq!(PerMeter::new(0.0), d[y][x]);
+            }
+        }
+    }
+
     #[test]
     fn test_calculate_nabla() {
         let g = sample_grid();
@@ -224,6 +502,44 @@ mod tests {
         assert_eq!(y, calculate_partial_difference_y(&g, delta));
     }
 
+    #[test]
+    fn test_calculate_gradient() {
+        let g = sample_grid();
+        let delta = Meter::new(10.0);
+
+        let gradient = calculate_gradient(&g, delta);
+        let x = calculate_partial_difference_x(&g, delta);
+        let y = calculate_partial_difference_y(&g, delta);
+        assert_eq!(g.size(), gradient.size());
+
+        for row in 0..3 {
+            for col in 0..3 {
+                assert_eq!((x[row][col], y[row][col]), gradient[row][col]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_calculate_gradient_magnitude() {
+        // f = 3x + 4y
+        let mut g = Grid::fill_default(3, 4);
+        for y in 0..3 {
+            for x in 0..4 {
+                g[y][x] = (3.0 * x as f64 + 4.0 * y as f64) * M;
+            }
+        }
+        let delta = Meter::new(1.0);
+
+        let d = calculate_gradient_magnitude(&g, delta);
+        assert_eq!(g.size(), d.size());
+
+        for y in 0..3 {
+            for x in 0..4 {
+                assert_eq!(Unitless::new(5.0), d[y][x]);
+            }
+        }
+    }
+
     #[test]
     fn test_calculate_laplacian() {
         let g = sample_grid();
@@ -242,4 +558,140 @@ mod tests {
         assert_eq!(PerMeter::new(0.01 + 0.04), d[2][1]);
         assert_eq!(PerMeter::new(0.02 + 0.04), d[2][2]);
     }
+
+    #[test]
+    fn test_calculate_hessian() {
+        // f = x^2 + 3xy + 2y^2
+        let mut g = Grid::fill_default(4, 4);
+        for row in 0..4 {
+            for col in 0..4 {
+                let (x, y) = (col as f64, row as f64);
+                g[row][col] = (x * x + 3.0 * x * y + 2.0 * y * y) * M;
+            }
+        }
+        let delta = Meter::new(1.0);
+
+        let (xx, yy, xy) = calculate_hessian(&g, delta);
+        assert_eq!(xx, calculate_partial_difference_xx(&g, delta));
+        assert_eq!(yy, calculate_partial_difference_yy(&g, delta));
+
+        for y in 1..=2 {
+            for x in 1..=2 {
+                assert_eq!(PerMeter::new(2.0), xx[y][x]);
+                assert_eq!(PerMeter::new(4.0), yy[y][x]);
+            }
+        }
+        for y in 0..4 {
+            for x in 0..4 {
+                assert_eq!(PerMeter::new(3.0), xy[y][x]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_calculate_biharmonic_edges() {
+        // f = x の ∇⁴f は0だが, 端を複製して延長するため外側2周は0にならない
+        let mut g = Grid::fill_default(6, 6);
+        for y in 0..6 {
+            for x in 0..6 {
+                g[y][x] = (x as f64) * M;
+            }
+        }
+        let delta = Meter::new(1.0);
+
+        let d = calculate_biharmonic(&g, delta);
+        let expected = [-2.0, 1.0, 0.0, 0.0, -1.0, 2.0];
+        for y in 0..6 {
+            for x in 0..6 {
+                assert_eq!(
+                    Meter::new(expected[x]),
+                    d[y][x] * (delta * delta) * (delta * delta)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_calculate_vector_laplacian() {
+        // (f, g) = (x^2 + y^2, 2x^2 - y^2)
+        let mut field = Grid::fill_default(4, 4);
+        for row in 0..4 {
+            for col in 0..4 {
+                let (x, y) = (col as f64, row as f64);
+                field[row][col] = ((x * x + y * y) * M, (2.0 * x * x - y * y) * M);
+            }
+        }
+        let delta = Meter::new(1.0);
+
+        let d = calculate_vector_laplacian(&field, delta);
+        assert_eq!(field.size(), d.size());
+
+        for y in 1..=2 {
+            for x in 1..=2 {
+                assert_eq!((PerMeter::new(4.0), PerMeter::new(2.0)), d[y][x]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_calculate_biharmonic() {
+        // f = x^4 + y^4 なので内部では ∇⁴f = 24 + 24
+        let mut g = Grid::fill_default(6, 6);
+        for y in 0..6 {
+            for x in 0..6 {
+                g[y][x] = ((x as f64).powi(4) + (y as f64).powi(4)) * M;
+            }
+        }
+        let delta = Meter::new(1.0);
+
+        let d = calculate_biharmonic(&g, delta);
+        assert_eq!(g.size(), d.size());
+
+        for y in 2..=3 {
+            for x in 2..=3 {
+                assert_eq!(
+                    Meter::new(48.0),
+                    d[y][x] * (delta * delta) * (delta * delta)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_convolve_matches_stencils() {
+        let g = sample_grid();
+        let delta = Meter::new(10.0);
+        let close = |a: PerMeter<f64>, b: PerMeter<f64>| (a - b).value_unsafe.abs() < 1e-12;
+
+        let xx_kernel = Grid::from_vec(vec![1.0, -2.0, 1.0], 3);
+        let yy_kernel = Grid::from_vec(vec![1.0, -2.0, 1.0], 1);
+        #[rustfmt::skip]
+        let laplacian_kernel = Grid::from_vec(vec![
+            0.0, 1.0, 0.0,
+            1.0, -4.0, 1.0,
+            0.0, 1.0, 0.0,
+        ], 3);
+
+        let xx = g
+            .convolve(&xx_kernel, PadMode::Replicate)
+            .unscale(delta * delta);
+        let yy = g
+            .convolve(&yy_kernel, PadMode::Replicate)
+            .unscale(delta * delta);
+        let laplacian = g
+            .convolve(&laplacian_kernel, PadMode::Replicate)
+            .unscale(delta * delta);
+
+        // 端の扱いは異なるので内部だけを比べる
+        let expected_xx = calculate_partial_difference_xx(&g, delta);
+        let expected_yy = calculate_partial_difference_yy(&g, delta);
+        let expected_laplacian = calculate_laplacian(&g, delta);
+        for y in 0..3 {
+            assert!(close(expected_xx[y][1], xx[y][1]));
+        }
+        for x in 0..3 {
+            assert!(close(expected_yy[1][x], yy[1][x]));
+        }
+        assert!(close(expected_laplacian[1][1], laplacian[1][1]));
+    }
 }
@@ -1,6 +1,8 @@
+mod error;
 mod grid;
 mod grid_diff;
 mod op_alias;
+mod poisson;
 mod universe;
 mod utils;
 
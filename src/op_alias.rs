@@ -1,4 +1,4 @@
-use std::ops::{Add, Div, Mul, Sub};
+use std::ops::{Add, Div, Mul, Neg, Sub};
 
 pub trait AddSelf: Sized + Add<Output = Self> {}
 
@@ -8,6 +8,10 @@ pub trait SubSelf: Sized + Sub<Output = Self> {}
 
 impl<T> SubSelf for T where T: Sub<Output = Self> {}
 
+pub trait NegSelf: Sized + Neg<Output = Self> {}
+
+impl<T> NegSelf for T where T: Neg<Output = Self> {}
+
 pub trait MulScalar<S>: Sized + Mul<S, Output = Self> {}
 
 impl<T, S> MulScalar<S> for T where T: Mul<S, Output = Self> {}
@@ -15,3 +19,19 @@ impl<T, S> MulScalar<S> for T where T: Mul<S, Output = Self> {}
 pub trait DivScalar<S>: Sized + Div<S, Output = Self> {}
 
 impl<T, S> DivScalar<S> for T where T: Div<S, Output = Self> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dimensioned::si::{Meter, M};
+
+    fn negate<T: NegSelf>(x: T) -> T {
+        -x
+    }
+
+    #[test]
+    fn test_neg_self() {
+        let x: Meter<f64> = negate(2.0 * M);
+        assert_eq!(-2.0 * M, x);
+    }
+}
@@ -0,0 +1,92 @@
+use crate::grid::Grid;
+use crate::grid_diff::calculate_laplacian;
+use crate::op_alias::{AddSelf, MulScalar, SubSelf};
+use dimensioned::typenum::Prod;
+use std::f64::consts::PI;
+use std::ops::{Div, Mul};
+
+/// Solves `∇²φ = 4πGρ` for the gravitational potential `φ` by Jacobi iteration.
+///
+/// `φ` is fixed to zero on the border of the grid.
+/// Iteration stops after `iterations` sweeps, or earlier once no entry changes by more than `tolerance` in a sweep.
+pub fn solve_poisson<R, G, U, S, P>(
+    rho: &Grid<R>,
+    delta: U,
+    g: G,
+    iterations: usize,
+    tolerance: P,
+) -> Grid<P>
+where
+    R: Copy + Mul<G, Output = S>,
+    G: Copy,
+    U: Copy + Mul<U>,
+    Prod<U, U>: Copy,
+    S: Copy + AddSelf + SubSelf + MulScalar<f64> + Mul<Prod<U, U>, Output = P>,
+    P: Copy
+        + Default
+        + AddSelf
+        + SubSelf
+        + MulScalar<f64>
+        + Div<Prod<U, U>, Output = S>
+        + PartialOrd,
+{
+    let (rows, cols) = rho.size();
+    let mut phi = Grid::<P>::fill_default(rows, cols);
+
+    for _ in 0..iterations {
+        let laplacian = calculate_laplacian(&phi, delta);
+        let mut converged = true;
+
+        // 境界の値は0に固定し, 内部のみ更新する
+        let mut next = phi.clone();
+        for y in 1..rows - 1 {
+            for x in 1..cols - 1 {
+                let correction =
+                    (laplacian[y][x] - (rho[y][x] * g) * (4.0 * PI)) * (delta * delta) * 0.25;
+                next[y][x] = phi[y][x] + correction;
+                if correction > tolerance || P::default() - correction > tolerance {
+                    converged = false;
+                }
+            }
+        }
+        phi = next;
+
+        if converged {
+            break;
+        }
+    }
+
+    phi
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dimensioned::si::{Meter, Meter2, Unitless};
+
+    #[test]
+    fn test_solve_poisson_manufactured_solution() {
+        let size = 9;
+        let delta = Meter::new(1.0);
+        let g = Unitless::new(1.0 / (4.0 * PI));
+
+        // 境界で0になる既知のポテンシャルから, 対応する密度を逆算する
+        let n = (size - 1) as f64;
+        let mut expected = Grid::fill_default(size, size);
+        for y in 0..size {
+            for x in 0..size {
+                let (fx, fy) = (x as f64, y as f64);
+                expected[y][x] = Meter2::new(fx * (n - fx) * fy * (n - fy));
+            }
+        }
+        let rho = calculate_laplacian(&expected, delta).map(|&l| l / (4.0 * PI) / g);
+
+        let phi = solve_poisson(&rho, delta, g, 10000, Meter2::new(1e-13));
+
+        for y in 0..size {
+            for x in 0..size {
+                assert!((phi[y][x] - expected[y][x]).value_unsafe.abs() < 1e-9);
+            }
+        }
+    }
+}
@@ -1,3 +1,6 @@
+use crate::op_alias::{AddSelf, SubSelf};
+use dimensioned::Dimensioned;
+
 pub fn set_panic_hook() {
     // When the `console_error_panic_hook` feature is enabled, we can call the
     // `set_panic_hook` function at least once during initialization, and then
@@ -8,3 +11,57 @@ pub fn set_panic_hook() {
     #[cfg(feature = "console_error_panic_hook")]
     console_error_panic_hook::set_once();
 }
+
+/// Sums `items` with Kahan compensation, which keeps the rounding error from growing with the number of items.
+///
+/// Once the sum becomes infinite or NaN the compensation is dropped, so the result follows plain floating-point addition.
+pub fn compensated_sum<T, I>(items: I) -> T
+where
+    T: Copy + Default + AddSelf + SubSelf + Dimensioned<Value = f64>,
+    I: IntoIterator<Item = T>,
+{
+    let mut sum = T::default();
+    let mut compensation = T::default();
+
+    for item in items {
+        let y = item - compensation;
+        let t = sum + y;
+        compensation = if t.value_unsafe().is_finite() {
+            (t - sum) - y
+        } else {
+            T::default()
+        };
+        sum = t;
+    }
+
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dimensioned::si::M;
+
+    #[test]
+    fn test_compensated_sum() {
+        let items = std::iter::once(1.0 * M).chain((0..10000).map(|_| 1e-16 * M));
+
+        let naive = items.clone().fold(0.0 * M, |acc, x| acc + x);
+        assert_eq!(1.0 * M, naive);
+
+        let sum = compensated_sum(items);
+        assert!((sum - (1.0 + 1e-12) * M).value_unsafe.abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_compensated_sum_non_finite() {
+        let sum = compensated_sum(vec![f64::INFINITY * M, 1.0 * M]);
+        assert_eq!(f64::INFINITY * M, sum);
+
+        let sum = compensated_sum(vec![f64::MAX * M, f64::MAX * M, 1.0 * M]);
+        assert_eq!(f64::INFINITY * M, sum);
+
+        let sum = compensated_sum(vec![f64::INFINITY * M, -f64::INFINITY * M]);
+        assert!(sum.value_unsafe.is_nan());
+    }
+}